@@ -0,0 +1,109 @@
+//! Configuration for `docs-helper`, loaded from an optional `docs-helper.toml`
+//! file so users can consolidate folders named something other than "docs"
+//! and add project-specific directories to skip during traversal. Modeled on
+//! starship's `StarshipConfig`: an optional file is read, deserialized with
+//! serde, and merged over built-in defaults.
+
+use std::fs;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::error::DocsHelperError;
+
+/// Default name of the directory searched for, case-insensitive.
+const DEFAULT_FIND_DIR: &str = "docs";
+
+/// Default directories skipped entirely during traversal. These are common
+/// project-related or dependency directories that do not typically contain
+/// relevant documentation.
+const DEFAULT_IGNORE_PATTERNS: [&str; 9] = [
+    "venv",
+    "site-packages",
+    "__pycache__",
+    "node_modules",
+    ".git",
+    "target",
+    "build",
+    "third_party",
+    "tests",
+];
+
+/// The shape of `docs-helper.toml` as written by users. Every field is
+/// optional so a config file only needs to mention what it overrides.
+#[derive(Debug, Default, Deserialize)]
+struct RawConfig {
+    find_dir: Option<String>,
+    ignore_patterns: Option<Vec<String>>,
+    additional_ignores: Option<Vec<String>>,
+}
+
+/// Resolved configuration used by the traversal and copy phases.
+#[derive(Debug, Clone)]
+pub struct DocsHelperConfig {
+    /// The name of the directory to search for, case-insensitive.
+    pub find_dir: String,
+    /// Full directory component names to skip entirely during traversal.
+    pub ignore_patterns: Vec<String>,
+}
+
+impl Default for DocsHelperConfig {
+    fn default() -> Self {
+        DocsHelperConfig {
+            find_dir: DEFAULT_FIND_DIR.to_string(),
+            ignore_patterns: DEFAULT_IGNORE_PATTERNS
+                .iter()
+                .map(|p| p.to_string())
+                .collect(),
+        }
+    }
+}
+
+impl DocsHelperConfig {
+    /// Loads the configuration, preferring an explicit `--config` path, then
+    /// falling back to `<root>/docs-helper.toml`, and finally to
+    /// `DocsHelperConfig::default()` if neither file exists.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `DocsHelperError` if a config path is given (or found under
+    /// `root`) but cannot be read or fails to parse as TOML.
+    pub fn load(root: &Path, config_path: Option<&Path>) -> Result<Self, DocsHelperError> {
+        let path = match config_path {
+            Some(explicit) => Some(explicit.to_path_buf()),
+            None => {
+                let candidate = root.join("docs-helper.toml");
+                if candidate.exists() {
+                    Some(candidate)
+                } else {
+                    None
+                }
+            }
+        };
+
+        let Some(path) = path else {
+            return Ok(Self::default());
+        };
+
+        let contents = fs::read_to_string(&path).map_err(|source| DocsHelperError::Io {
+            path: path.clone(),
+            source,
+        })?;
+        let raw: RawConfig = toml::from_str(&contents).map_err(|e| DocsHelperError::Config {
+            path: path.clone(),
+            message: e.to_string(),
+        })?;
+
+        let mut config = Self::default();
+        if let Some(find_dir) = raw.find_dir {
+            config.find_dir = find_dir;
+        }
+        if let Some(ignore_patterns) = raw.ignore_patterns {
+            config.ignore_patterns = ignore_patterns;
+        }
+        if let Some(additional) = raw.additional_ignores {
+            config.ignore_patterns.extend(additional);
+        }
+        Ok(config)
+    }
+}
@@ -0,0 +1,85 @@
+//! Crate-wide error type for `docs-helper`.
+//!
+//! Following cargo's convention of wrapping `fs` calls so a failure always
+//! carries the path it was operating on, every I/O failure this crate can
+//! hit is reported through `DocsHelperError` rather than a bare `io::Error`
+//! or a `panic!`.
+
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+
+/// An error produced by docs-helper, always annotated with the path that was
+/// being operated on when it occurred.
+#[derive(Debug)]
+pub enum DocsHelperError {
+    /// `fs::canonicalize` (or similar path resolution) failed, typically
+    /// because the path does not exist.
+    ResolvePath { path: PathBuf, source: io::Error },
+    /// A filesystem operation (create, remove, copy, rename, walk, ...) failed.
+    Io { path: PathBuf, source: io::Error },
+    /// A destination already existed and neither `overwrite` nor
+    /// `skip_existing` allowed the run to proceed.
+    AlreadyExists { path: PathBuf },
+    /// A path could not be used as required, e.g. it has no file name, no
+    /// parent, or is not valid UTF-8.
+    InvalidPath { path: PathBuf, message: String },
+    /// The config file at `path` could not be parsed.
+    Config { path: PathBuf, message: String },
+    /// One or more docs directories failed to copy; `errors` holds one
+    /// message per failure so the run's final report covers every one of
+    /// them instead of just the first.
+    Partial { errors: Vec<String> },
+}
+
+impl fmt::Display for DocsHelperError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DocsHelperError::ResolvePath { path, source } => {
+                write!(f, "could not resolve path {}: {}", path.display(), source)
+            }
+            DocsHelperError::Io { path, source } => {
+                write!(f, "I/O error at {}: {}", path.display(), source)
+            }
+            DocsHelperError::AlreadyExists { path } => {
+                write!(f, "destination already exists: {}", path.display())
+            }
+            DocsHelperError::InvalidPath { path, message } => {
+                write!(f, "invalid path {}: {}", path.display(), message)
+            }
+            DocsHelperError::Config { path, message } => {
+                write!(f, "invalid config {}: {}", path.display(), message)
+            }
+            DocsHelperError::Partial { errors } => {
+                writeln!(
+                    f,
+                    "{} docs director{} failed to copy:",
+                    errors.len(),
+                    if errors.len() == 1 { "y" } else { "ies" }
+                )?;
+                for (i, error) in errors.iter().enumerate() {
+                    if i > 0 {
+                        writeln!(f)?;
+                    }
+                    write!(f, "  - {}", error)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Error for DocsHelperError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            DocsHelperError::ResolvePath { source, .. } | DocsHelperError::Io { source, .. } => {
+                Some(source)
+            }
+            DocsHelperError::AlreadyExists { .. }
+            | DocsHelperError::InvalidPath { .. }
+            | DocsHelperError::Config { .. }
+            | DocsHelperError::Partial { .. } => None,
+        }
+    }
+}
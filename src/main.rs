@@ -7,27 +7,49 @@
 use std::fs;
 use std::io::{self};
 use std::path::{Path, PathBuf};
-use std::process::Command;
+use std::sync::atomic::{AtomicU16, Ordering};
+use std::sync::Mutex;
 
+use rayon::prelude::*;
+use serde::Serialize;
 use walkdir::{DirEntry, WalkDir};
 
-/// The name of the directory to search for, case-insensitive.
-const FIND_THIS_DIR: &str = "docs";
-
-/// Directories that should be skipped entirely during the traversal.
-/// These are common project-related or dependency directories that do not
-/// typically contain relevant documentation.
-const DEFAULT_IGNORE_PATTERNS: [&str; 9] = [
-    "venv",
-    "site-packages",
-    "__pycache__",
-    "node_modules",
-    ".git",
-    "target",
-    "build",
-    "third_party",
-    "tests",
-];
+mod config;
+mod error;
+#[cfg(test)]
+mod tests;
+
+use config::DocsHelperConfig;
+use error::DocsHelperError;
+
+/// Options controlling how `copy_docs_dir` behaves when the destination
+/// already contains files, mirroring the knobs `fs_extra`'s `dir::CopyOptions`
+/// exposes.
+#[derive(Debug, Clone)]
+struct CopyOptions {
+    /// Overwrite destination files that already exist.
+    overwrite: bool,
+    /// When `overwrite` is `false`, silently skip existing destination files
+    /// instead of returning an error.
+    skip_existing: bool,
+    /// Copy the children of `src` directly into `dest` instead of recreating
+    /// a directory named after `src` inside it.
+    content_only: bool,
+    /// Maximum depth `WalkDir` is allowed to descend into `src`, `None` for
+    /// unlimited.
+    max_depth: Option<usize>,
+}
+
+impl Default for CopyOptions {
+    fn default() -> Self {
+        CopyOptions {
+            overwrite: true,
+            skip_existing: false,
+            content_only: true,
+            max_depth: None,
+        }
+    }
+}
 
 /// Normalizes a given path string by ensuring it ends with a trailing slash.
 /// If the path already ends with a slash, it is returned as is.
@@ -56,87 +78,189 @@ fn normalize_path(path: &str) -> String {
 }
 
 /// Resolves a given path to its canonical, absolute form.
-/// This function will panic if the path cannot be resolved.
 ///
 /// # Arguments
 ///
 /// * `p` - A string slice representing the path to resolve.
 ///
-/// # Returns
-///
-/// A `String` containing the canonicalized path.
-///
-/// # Panics
+/// # Errors
 ///
-/// Panics if the path cannot be canonicalized (e.g., if it does not exist).
+/// Returns `DocsHelperError::ResolvePath` if the path cannot be canonicalized
+/// (e.g., if it does not exist), carrying the offending path.
 ///
 /// # Examples
 ///
 /// ```no_run
 /// // Assuming "/tmp" exists
-/// let resolved = resolve_path("/tmp/../tmp");
+/// let resolved = resolve_path("/tmp/../tmp").unwrap();
 /// // On Unix, this might resolve to "/private/tmp" or "/tmp"
 /// // assert!(resolved.ends_with("/tmp/"));
 /// ```
-fn resolve_path(p: &str) -> String {
+fn resolve_path(p: &str) -> Result<String, DocsHelperError> {
     fs::canonicalize(Path::new(p))
-        .unwrap_or_else(|e| panic!("Could not resolve path: {} --err={}", p, e))
-        .to_string_lossy()
-        .to_string()
+        .map(|resolved| resolved.to_string_lossy().to_string())
+        .map_err(|source| DocsHelperError::ResolvePath {
+            path: PathBuf::from(p),
+            source,
+        })
 }
 
-/// Cleans up the target directory by recursively removing its contents if it exists,
-/// and then recreating it. This ensures a clean slate for copying documentation.
+/// Creates an empty directory next to `target`, e.g. `<parent>/<target-name>.<label>-<random>`,
+/// on the same filesystem as `target` so a later `fs::rename` onto/from `target`'s path is atomic.
 ///
 /// # Arguments
 ///
-/// * `path` - A string slice representing the path to the target directory.
+/// * `target` - The final output directory the new sibling is created next to.
+/// * `label` - Distinguishes what the sibling is for, e.g. `"tmp"` for a
+///   staging directory or `"bak"` for a pre-swap backup.
 ///
 /// # Errors
 ///
-/// Returns an `io::Result` indicating whether the operation was successful.
-/// An error is returned if directory removal or creation fails.
+/// Returns an `io::Result` if the sibling directory cannot be created, e.g.
+/// because `target`'s parent does not exist or is not writable.
+fn sibling_tempdir(target: &Path, label: &str) -> Result<tempfile::TempDir, DocsHelperError> {
+    let parent = target.parent().filter(|p| !p.as_os_str().is_empty());
+    let parent = parent.unwrap_or_else(|| Path::new("."));
+    let name = target.file_name().and_then(|n| n.to_str()).unwrap_or("out");
+
+    tempfile::Builder::new()
+        .prefix(&format!("{}.{}-", name, label))
+        .tempdir_in(parent)
+        .map_err(|source| DocsHelperError::Io {
+            path: parent.to_path_buf(),
+            source,
+        })
+}
+
+/// Creates a sibling staging directory next to `target` that a full-replace
+/// run copies into instead of writing `target` directly.
+///
+/// Staging outside of `target` means a crash or Ctrl-C mid-run never leaves
+/// the real output half-populated: the previous good `target` (if any) is
+/// left completely untouched until `commit_staging_dir` swaps the staging
+/// directory into place.
 ///
-/// # Examples
+/// # Arguments
 ///
-/// ```no_run
-/// use std::fs;
-/// use std::io;
-/// // Create a dummy directory for testing
-/// let _ = fs::create_dir_all("test_target/subdir");
-/// let _ = fs::write("test_target/subdir/file.txt", "content");
+/// * `target` - The final output directory the run is consolidating into.
 ///
-/// // Clean up the directory
-/// cleanup_dir("test_target").unwrap();
+/// # Errors
 ///
-/// // Assert that the directory exists but is empty
-/// assert!(fs::metadata("test_target").unwrap().is_dir());
-/// assert!(fs::read_dir("test_target").unwrap().next().is_none());
+/// Returns an `io::Result` if the staging directory cannot be created, e.g.
+/// because `target`'s parent does not exist or is not writable.
+fn create_staging_dir(target: &Path) -> Result<tempfile::TempDir, DocsHelperError> {
+    sibling_tempdir(target, "tmp")
+}
+
+/// Swaps a populated staging directory into `target`.
 ///
-/// // Clean up the created directory
-/// let _ = fs::remove_dir("test_target");
-/// ```
-fn cleanup_dir(path: &str) -> io::Result<()> {
-    println!("Cleaning the target dir: {}", path);
-    let target = PathBuf::from(path);
+/// If `target` already exists, it is first renamed aside to a sibling backup
+/// directory (a single, near-instant `fs::rename` rather than a recursive
+/// `remove_dir_all`), the staging directory is then renamed onto `target`'s
+/// now-vacant path, and finally the backup is removed on a best-effort basis.
+/// `target`'s name is therefore never left pointing at nothing for longer
+/// than the gap between those two renames, and if the process dies in that
+/// gap the previous contents are still recoverable from the backup directory
+/// rather than having been deleted outright.
+///
+/// # Arguments
+///
+/// * `staging` - The staging directory created by `create_staging_dir`, now
+///   fully populated.
+/// * `target` - The final output directory path to replace.
+///
+/// # Errors
+///
+/// Returns a `DocsHelperError` if renaming the previous `target` aside or
+/// renaming the staging directory into place fails. Failing to remove the
+/// leftover backup directory afterwards is not treated as an error.
+fn commit_staging_dir(staging: tempfile::TempDir, target: &Path) -> Result<(), DocsHelperError> {
+    let staging_path = staging.keep();
+
     if target.exists() {
-        fs::remove_dir_all(&target)?;
+        let backup = sibling_tempdir(target, "bak")?;
+        let backup_path = backup.keep();
+        fs::rename(target, &backup_path).map_err(|source| DocsHelperError::Io {
+            path: target.to_path_buf(),
+            source,
+        })?;
+        fs::rename(&staging_path, target).map_err(|source| DocsHelperError::Io {
+            path: target.to_path_buf(),
+            source,
+        })?;
+        let _ = fs::remove_dir_all(&backup_path);
+    } else {
+        fs::rename(&staging_path, target).map_err(|source| DocsHelperError::Io {
+            path: target.to_path_buf(),
+            source,
+        })?;
+    }
+
+    Ok(())
+}
+
+/// Recreates a single file or symlink found while walking `src` at the matching
+/// location under `dest`.
+///
+/// `entry` must be a file or a symlink; directories are created separately by
+/// the caller via `fs::create_dir_all` before their children are visited.
+fn copy_entry(entry: &DirEntry, relative: &Path, dest_root: &Path) -> io::Result<()> {
+    let dest_path = dest_root.join(relative);
+    if let Some(parent) = dest_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let file_type = entry.file_type();
+    if file_type.is_symlink() {
+        let link_target = fs::read_link(entry.path())?;
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(&link_target, &dest_path)?;
+        #[cfg(windows)]
+        {
+            if link_target.is_dir() {
+                std::os::windows::fs::symlink_dir(&link_target, &dest_path)?;
+            } else {
+                std::os::windows::fs::symlink_file(&link_target, &dest_path)?;
+            }
+        }
+        return Ok(());
+    }
+
+    fs::copy(entry.path(), &dest_path)?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = fs::metadata(entry.path())?.permissions().mode();
+        fs::set_permissions(&dest_path, fs::Permissions::from_mode(mode))?;
     }
-    fs::create_dir_all(&target)?;
+
     Ok(())
 }
 
-/// Copies a directory from a source path to a destination path using the `cp -r` command.
+/// Recursively copies the contents of `src` into `dest`, reproducing the
+/// source's directory structure without shelling out to an external `cp`.
+///
+/// Every entry under `src` is visited with `WalkDir`, its path relative to
+/// `src` is computed by stripping the source prefix, and that relative path
+/// is recreated under `dest`: directories via `fs::create_dir_all`, and files
+/// via `fs::copy`. Symlinks are not followed during the walk (`follow_links`
+/// is `false`) and are instead recreated as symlinks at the destination,
+/// matching `rsync`'s default of preserving rather than dereferencing them.
 ///
 /// # Arguments
 ///
 /// * `src` - A string slice representing the source directory path.
 /// * `dest` - A string slice representing the destination directory path.
+/// * `options` - A `CopyOptions` controlling overwrite, skip-existing,
+///   content-only, and max-depth behavior.
 ///
 /// # Errors
 ///
-/// Returns an `io::Result` indicating whether the operation was successful.
-/// An error is returned if the `cp` command fails or returns a non-zero exit status.
+/// Returns a `DocsHelperError`, annotated with the offending path, if any
+/// directory creation, file copy, or symlink recreation fails, or if a
+/// destination file already exists and `options.overwrite` is `false` while
+/// `options.skip_existing` is also `false`.
 ///
 /// # Examples
 ///
@@ -149,7 +273,7 @@ fn cleanup_dir(path: &str) -> io::Result<()> {
 /// let _ = fs::create_dir_all(\"target_dir\");
 ///
 /// // Copy the docs directory
-/// copy_docs_dir(\"source_dir/docs\", \"target_dir/copied_docs\").unwrap();
+/// copy_docs_dir(\"source_dir/docs\", \"target_dir/copied_docs\", &Default::default()).unwrap();
 ///
 /// assert!(fs::metadata(\"target_dir/copied_docs/file.txt\").unwrap().is_file());
 ///
@@ -157,29 +281,84 @@ fn cleanup_dir(path: &str) -> io::Result<()> {
 /// let _ = fs::remove_dir_all(\"source_dir\");
 /// let _ = fs::remove_dir_all(\"target_dir\");
 /// ```
-fn copy_docs_dir(src: &str, dest: &str) -> io::Result<()> {
+fn copy_docs_dir(src: &str, dest: &str, options: &CopyOptions) -> Result<(), DocsHelperError> {
     let src = normalize_path(src);
     let dest = normalize_path(dest);
+    let src_root = Path::new(&src);
+    let dest_root = if options.content_only {
+        PathBuf::from(&dest)
+    } else {
+        let name = src_root.file_name().ok_or_else(|| DocsHelperError::InvalidPath {
+            path: src_root.to_path_buf(),
+            message: "source has no file name".to_string(),
+        })?;
+        PathBuf::from(&dest).join(name)
+    };
 
-    let output = Command::new("cp").arg("-r").arg(&src).arg(&dest).output()?;
+    fs::create_dir_all(&dest_root).map_err(|source| DocsHelperError::Io {
+        path: dest_root.clone(),
+        source,
+    })?;
 
-    if output.status.success() {
-        Ok(())
-    } else {
-        Err(io::Error::new(
-            io::ErrorKind::Other,
-            String::from_utf8_lossy(&output.stderr).trim().to_string(),
-        ))
+    let mut walker = WalkDir::new(src_root).follow_links(false);
+    if let Some(max_depth) = options.max_depth {
+        walker = walker.max_depth(max_depth);
+    }
+
+    for entry in walker {
+        let entry = entry.map_err(|e| {
+            let path = e.path().map(Path::to_path_buf).unwrap_or_else(|| src_root.to_path_buf());
+            DocsHelperError::Io {
+                path,
+                source: io::Error::from(e),
+            }
+        })?;
+        let relative = entry.path().strip_prefix(src_root).map_err(|e| DocsHelperError::InvalidPath {
+            path: entry.path().to_path_buf(),
+            message: e.to_string(),
+        })?;
+        if relative.as_os_str().is_empty() {
+            continue;
+        }
+
+        if entry.file_type().is_dir() {
+            let dir_path = dest_root.join(relative);
+            fs::create_dir_all(&dir_path).map_err(|source| DocsHelperError::Io {
+                path: dir_path,
+                source,
+            })?;
+            continue;
+        }
+
+        let dest_path = dest_root.join(relative);
+        if dest_path.exists() && !options.overwrite {
+            if options.skip_existing {
+                continue;
+            }
+            return Err(DocsHelperError::AlreadyExists { path: dest_path });
+        }
+        copy_entry(&entry, relative, &dest_root).map_err(|source| DocsHelperError::Io {
+            path: dest_path,
+            source,
+        })?;
     }
+
+    Ok(())
 }
 
 /// Determines whether a given directory entry should be traversed by the `WalkDir` iterator.
 /// This function filters out non-directories, hidden directories (starting with '.'),
-/// special directories (starting with '_'), and directories matching `DEFAULT_IGNORE_PATTERNS`.
+/// special directories (starting with '_'), and directories matching
+/// `config.ignore_patterns`.
+///
+/// Ignore patterns are matched against the *full* directory component name,
+/// not as a substring, so an ignore pattern of "build" skips a directory
+/// named "build" but not one named "build-notes".
 ///
 /// # Arguments
 ///
 /// * `entry` - A reference to a `DirEntry` to evaluate.
+/// * `config` - The resolved configuration supplying the ignore patterns.
 ///
 /// # Returns
 ///
@@ -192,34 +371,35 @@ fn copy_docs_dir(src: &str, dest: &str) -> io::Result<()> {
 /// use std::path::PathBuf;
 /// // Assume a DirEntry `entry` for a directory named "my_project"
 /// // let entry: DirEntry = ...;
-/// // assert_eq!(should_traverse(&entry), true);
+/// // assert_eq!(should_traverse(&entry, &config), true);
 ///
 /// // Assume a DirEntry `entry_hidden` for a directory named ".git"
 /// // let entry_hidden: DirEntry = ...;
-/// // assert_eq!(should_traverse(&entry_hidden), false);
+/// // assert_eq!(should_traverse(&entry_hidden, &config), false);
 /// ```
-fn should_traverse(entry: &DirEntry) -> bool {
+fn should_traverse(entry: &DirEntry, config: &DocsHelperConfig) -> bool {
     let name = entry.file_name().to_string_lossy();
     print!("\x1B[2K\rScanning {}", name);
     if !entry.file_type().is_dir() || name.starts_with('.') || name.starts_with('_') {
         return false;
     }
-    if DEFAULT_IGNORE_PATTERNS.iter().any(|p| name.contains(p)) {
+    if config.ignore_patterns.iter().any(|p| name == p.as_str()) {
         return false;
     }
-    return true;
+    true
 }
 
 /// Filters `DirEntry` objects, returning true only for directories
-/// that are named "docs" (case-insensitive).
+/// that are named `config.find_dir` (case-insensitive).
 ///
 /// # Arguments
 ///
 /// * `entry` - A reference to a `DirEntry` to evaluate.
+/// * `config` - The resolved configuration supplying the directory name to match.
 ///
 /// # Returns
 ///
-/// `true` if the entry is a directory named "docs", `false` otherwise.
+/// `true` if the entry is a directory named `config.find_dir`, `false` otherwise.
 ///
 /// # Examples
 ///
@@ -228,45 +408,330 @@ fn should_traverse(entry: &DirEntry) -> bool {
 /// use std::path::PathBuf;
 /// // Assume a DirEntry `entry_docs` for a directory named "docs"
 /// // let entry_docs: DirEntry = ...;
-/// // assert_eq!(is_docs_dir(&entry_docs), true);
+/// // assert_eq!(is_docs_dir(&entry_docs, &config), true);
 ///
 /// // Assume a DirEntry `entry_other` for a directory named "src"
 /// // let entry_other: DirEntry = ...;
-/// // assert_eq!(is_docs_dir(&entry_other), false);
+/// // assert_eq!(is_docs_dir(&entry_other, &config), false);
 /// ```
-fn is_docs_dir(entry: &DirEntry) -> bool {
+fn is_docs_dir(entry: &DirEntry, config: &DocsHelperConfig) -> bool {
     entry.file_type().is_dir()
         && entry
             .file_name()
             .to_string_lossy()
-            .eq_ignore_ascii_case(FIND_THIS_DIR)
+            .eq_ignore_ascii_case(&config.find_dir)
+}
+
+/// Walks `root`, applying `config`'s traversal and matching rules, and
+/// collects every directory found that should be consolidated.
+fn collect_docs_dirs(root: &str, config: &DocsHelperConfig) -> Vec<PathBuf> {
+    WalkDir::new(root)
+        .follow_links(false)
+        .into_iter()
+        .filter_entry(|entry| should_traverse(entry, config))
+        .filter_map(Result::ok)
+        .filter(|entry| is_docs_dir(entry, config))
+        .map(|e| e.path().to_path_buf())
+        .collect()
+}
+
+/// Computes the destination path a docs directory found at `entry_path`
+/// would be copied to under `target_root`: strip `root` off the front of
+/// `entry_path`, take what's left minus the docs directory's own name, and
+/// collapse out every remaining path component matching `config.find_dir`
+/// (case-insensitively, the same comparison `is_docs_dir` uses) — handling
+/// docs directories nested inside other docs directories, which `should_traverse`
+/// doesn't exclude from traversal. When `options.content_only` is `false`,
+/// re-appends the docs directory's own name, mirroring `copy_docs_dir`'s
+/// equivalent branch.
+///
+/// Shared by the real copy loop and `--dry-run` so the preview a dry run
+/// prints always matches what an actual run would do.
+///
+/// # Errors
+///
+/// Returns `DocsHelperError::InvalidPath` if `entry_path` is not under
+/// `root`, has no parent directory to consolidate into, or (when
+/// `!options.content_only`) has no file name.
+fn plan_destination(
+    entry_path: &Path,
+    root: &str,
+    target_root: &str,
+    config: &DocsHelperConfig,
+    options: &CopyOptions,
+) -> Result<PathBuf, DocsHelperError> {
+    let relative = entry_path
+        .strip_prefix(root)
+        .map_err(|e| DocsHelperError::InvalidPath {
+            path: entry_path.to_path_buf(),
+            message: e.to_string(),
+        })?;
+    let relative_parent = relative.parent().ok_or_else(|| DocsHelperError::InvalidPath {
+        path: entry_path.to_path_buf(),
+        message: "docs directory has no parent to consolidate into".to_string(),
+    })?;
+
+    let collapsed: PathBuf = relative_parent
+        .components()
+        .filter(|component| match component {
+            std::path::Component::Normal(name) => {
+                !name.to_string_lossy().eq_ignore_ascii_case(&config.find_dir)
+            }
+            _ => true,
+        })
+        .collect();
+    let destination = PathBuf::from(target_root).join(collapsed);
+
+    if options.content_only {
+        Ok(destination)
+    } else {
+        let name = entry_path.file_name().ok_or_else(|| DocsHelperError::InvalidPath {
+            path: entry_path.to_path_buf(),
+            message: "docs directory has no file name".to_string(),
+        })?;
+        Ok(destination.join(name))
+    }
+}
+
+/// A single planned `source -> destination` mapping, as printed or written
+/// to a `--manifest` file during a `--dry-run`.
+#[derive(Debug, Serialize)]
+struct ManifestEntry {
+    source: String,
+    destination: String,
+}
+
+/// Parses the optional flags following the `<root> <target>` positional
+/// arguments into a `CopyOptions`, starting from `CopyOptions::default()`.
+///
+/// Recognized flags: `--overwrite` (clobber existing destination files),
+/// `--no-overwrite` (keep existing destination files, erroring on any
+/// conflict), `--skip-existing` (silently keep existing destination files
+/// instead of erroring; implies `--no-overwrite`), `--no-content-only`
+/// (recreate the source directory's own name under the target instead of
+/// copying just its children), and `--max-depth <N>` (cap how deep the copy
+/// descends). Unrecognized flags are reported and otherwise ignored.
+///
+/// # Arguments
+///
+/// * `args` - The CLI arguments following the root and target paths.
+fn parse_copy_options(args: &[String]) -> CopyOptions {
+    let mut options = CopyOptions::default();
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--overwrite" => options.overwrite = true,
+            "--no-overwrite" => options.overwrite = false,
+            "--skip-existing" => {
+                options.overwrite = false;
+                options.skip_existing = true;
+            }
+            "--no-content-only" => options.content_only = false,
+            "--max-depth" => {
+                i += 1;
+                match args.get(i).and_then(|v| v.parse().ok()) {
+                    Some(depth) => options.max_depth = Some(depth),
+                    None => eprintln!("--max-depth requires a numeric argument"),
+                }
+            }
+            // Consumed separately by `parse_config_path`.
+            "--config" => {
+                i += 1;
+            }
+            // Consumed separately by `run`/`parse_manifest_path`.
+            "--dry-run" => {}
+            "--manifest" => {
+                i += 1;
+            }
+            other => eprintln!("ignoring unrecognized flag: {}", other),
+        }
+        i += 1;
+    }
+    options
+}
+
+/// Scans the optional flags for `--config <path>`, returning the path to an
+/// explicit config file if one was given.
+///
+/// # Arguments
+///
+/// * `args` - The CLI arguments following the root and target paths.
+fn parse_config_path(args: &[String]) -> Option<PathBuf> {
+    args.iter()
+        .position(|a| a == "--config")
+        .and_then(|i| args.get(i + 1))
+        .map(PathBuf::from)
+}
+
+/// Scans the optional flags for `--manifest <path>`, returning the file a
+/// `--dry-run`'s manifest should be written to, if given. Without it, a
+/// dry-run prints the manifest to stdout instead.
+///
+/// # Arguments
+///
+/// * `args` - The CLI arguments following the root and target paths.
+fn parse_manifest_path(args: &[String]) -> Option<PathBuf> {
+    args.iter()
+        .position(|a| a == "--manifest")
+        .and_then(|i| args.get(i + 1))
+        .map(PathBuf::from)
 }
 
-/// The main function of the documentation helper utility.
-/// It takes two command-line arguments: a root directory to scan and a target directory
+/// Copies every docs directory found under `root` into `dest_root`,
+/// honoring `config`'s traversal/matching rules and `options`'s per-file
+/// overwrite/skip-existing/content-only/max-depth behavior.
+///
+/// Runs the per-directory copies concurrently via rayon, printing progress
+/// as each one finishes. A failed copy does not stop the others: every
+/// failure is collected and reported together once the whole pass is done.
+///
+/// # Errors
+///
+/// Returns `DocsHelperError::Partial` if any docs directory failed to copy.
+fn copy_all(
+    root: &str,
+    dest_root: &str,
+    config: &DocsHelperConfig,
+    options: &CopyOptions,
+) -> Result<(), DocsHelperError> {
+    let docs_dirs: Vec<PathBuf> = collect_docs_dirs(root, config);
+    let total: usize = docs_dirs.len();
+
+    println!("\x1B[2K\rFound {} docs directories", total);
+    let done = AtomicU16::new(0);
+    let errors: Mutex<Vec<String>> = Mutex::new(Vec::new());
+    let width = ((total as f64).log10().floor() as usize) + 1;
+
+    docs_dirs.par_iter().for_each(|entry_path| {
+        let relative = match entry_path.strip_prefix(root) {
+            Ok(relative) => relative,
+            Err(e) => {
+                errors.lock().unwrap().push(format!(
+                    "failed to compute relative path for {}: {}",
+                    entry_path.display(),
+                    e
+                ));
+                return;
+            }
+        };
+
+        let constructed_path = match plan_destination(entry_path, root, dest_root, config, options) {
+            Ok(path) => path,
+            Err(e) => {
+                errors.lock().unwrap().push(format!(
+                    "failed to plan destination for {}: {}",
+                    entry_path.display(),
+                    e
+                ));
+                return;
+            }
+        };
+
+        // Ensure parent directories exist before copying
+        if let Some(parent) = constructed_path.parent() {
+            if let Err(e) = fs::create_dir_all(parent) {
+                errors
+                    .lock()
+                    .unwrap()
+                    .push(format!("failed to create parent {}: {}", parent.display(), e));
+                return;
+            }
+        }
+
+        let (Some(src), Some(dest)) = (entry_path.to_str(), constructed_path.to_str()) else {
+            errors.lock().unwrap().push(format!(
+                "path is not valid UTF-8: {} -> {}",
+                entry_path.display(),
+                constructed_path.display()
+            ));
+            return;
+        };
+
+        // `constructed_path` (and thus `dest`) already accounts for
+        // `options.content_only` via `plan_destination`, so `copy_docs_dir`
+        // must not re-apply that nesting decision on top of it.
+        let copy_options = CopyOptions {
+            content_only: true,
+            ..options.clone()
+        };
+
+        // Perform the copy operation
+        if let Err(e) = copy_docs_dir(src, dest, &copy_options) {
+            errors.lock().unwrap().push(format!(
+                "failed copying {} -> {}: {}",
+                entry_path.display(),
+                constructed_path.display(),
+                e
+            ));
+        } else {
+            // Increment counter and print progress
+            let done = done.fetch_add(1, Ordering::SeqCst) + 1;
+
+            println!(
+                "({:0width$}/{:0width$}) finished copying {} ",
+                done,
+                total,
+                relative.display()
+            );
+        }
+    });
+
+    let errors = errors.into_inner().unwrap();
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(DocsHelperError::Partial { errors })
+    }
+}
+
+/// Entry point. Delegates to `run` and, on failure, prints a single
+/// actionable error message and exits non-zero instead of unwinding with a
+/// panic and stack trace.
+fn main() {
+    if let Err(e) = run() {
+        eprintln!("error: {}", e);
+        std::process::exit(1);
+    }
+}
+
+/// Consolidates "docs" directories found under a root into a target directory.
+/// Takes two command-line arguments: a root directory to scan and a target directory
 /// where the consolidated documentation will be placed.
 ///
 /// It performs the following steps:
 /// 1. Parses command-line arguments and validates their count.
 /// 2. Resolves and normalizes the root and target paths.
-/// 3. Cleans up the target directory.
-/// 4. Walks the root directory, filtering for "docs" directories using `should_traverse`
-///    and `is_docs_dir`.
-/// 5. For each found "docs" directory, it constructs a new path in the target
-///    directory, removing the "docs" segment from the relative path.
-/// 6. Copies the contents of the "docs" directory to the newly constructed path.
-/// 7. Prints progress and error messages during the copying process.
-/// 8. On successful completion, prints a success message.
+/// 3. If `--dry-run` was given, hands off to `run_dry_run` and returns,
+///    touching neither a staging directory nor the target.
+/// 4. Otherwise, dispatches to one of two modes based on `options.overwrite`:
+///    - `overwrite` (the default): a full-replace run. Copies into a sibling
+///      staging directory via `create_staging_dir`/`copy_all`, then swaps it
+///      onto `target` via `commit_staging_dir` so a crash mid-run never
+///      leaves `target` half-populated — it is left exactly as it was.
+///    - non-`overwrite` (`--no-overwrite`/`--skip-existing`): an incremental
+///      run that copies directly into `target`, so that docs directories
+///      placed there by an earlier run accumulate instead of being wiped by
+///      an empty staging directory. `options.skip_existing` and `overwrite`
+///      still govern what happens to any individual file `target` already
+///      has, exactly as they do in the full-replace mode.
+/// 5. `copy_all` walks the root directory, filtering for "docs" directories
+///    using `should_traverse` and `is_docs_dir`, and for each one constructs
+///    its destination path (removing the "docs" segment) and copies into it,
+///    printing progress and collecting any per-directory errors.
 ///
 /// # Arguments
 ///
-/// * `args` - Command line arguments: `[0]` - program name, `[1]` - root directory, `[2]` - target directory.
+/// * `args` - Command line arguments: `[0]` - program name, `[1]` - root directory,
+///   `[2]` - target directory, followed by optional flags: `--overwrite`,
+///   `--no-overwrite`, `--skip-existing`, `--no-content-only`, `--max-depth <N>`,
+///   `--config <path>`, `--dry-run`, `--manifest <path>`.
 ///
 /// # Errors
 ///
-/// Returns an `io::Result` indicating whether the overall operation was successful.
-/// Errors can occur during path resolution, directory cleanup, directory creation,
-/// or file copying.
+/// Returns a `DocsHelperError`, annotated with the offending path, if path
+/// resolution, config loading, staging directory creation, or directory
+/// creation fails before the copy phase starts, or `DocsHelperError::Partial`
+/// if one or more docs directories failed to copy.
 ///
 /// # Examples
 ///
@@ -286,76 +751,105 @@ fn is_docs_dir(entry: &DirEntry) -> bool {
 /// but omitting the "docs" segment. For example, if you have `my_project/src/docs`
 /// and `my_project/api/docs`, their contents would be copied to `dist/src` and `dist/api`
 /// respectively.
-fn main() -> io::Result<()> {
+fn run() -> Result<(), DocsHelperError> {
     let args: Vec<String> = std::env::args().collect();
-    if args.len() != 3 {
-        eprintln!("Usage: {} <root> <target>", args[0]);
+    if args.len() < 3 {
+        eprintln!(
+            "Usage: {} <root> <target> [--overwrite] [--no-overwrite] [--skip-existing] [--no-content-only] [--max-depth <N>] [--config <path>] [--dry-run] [--manifest <path>]",
+            args[0]
+        );
         std::process::exit(1);
     }
 
-    let root: String = resolve_path(&normalize_path(&args[1]));
+    let root: String = resolve_path(&normalize_path(&args[1]))?;
     let target: String = normalize_path(&args[2]);
+    let options = parse_copy_options(&args[3..]);
+    let config_path = parse_config_path(&args[3..]);
+    let config = DocsHelperConfig::load(Path::new(&root), config_path.as_deref())?;
 
-    cleanup_dir(&target)?;
-    println!("root={}; target={}", root, target);
+    if args[3..].iter().any(|a| a == "--dry-run") {
+        let manifest_path = parse_manifest_path(&args[3..]);
+        return run_dry_run(&root, &target, &config, &options, manifest_path.as_deref());
+    }
 
-    // Collect all docs directories first to get total count
-    let docs_dirs: Vec<PathBuf> = WalkDir::new(&root)
-        .follow_links(false)
-        .into_iter()
-        .filter_entry(should_traverse)
-        .filter_map(Result::ok)
-        .filter(is_docs_dir)
-        .map(|e| e.path().to_path_buf())
-        .collect();
+    let target_path = Path::new(&target);
+    println!("root={}; target={}", root, target);
 
-    let total: usize = docs_dirs.len();
+    if options.overwrite {
+        // Full-replace run: stage into a sibling temp dir and swap it into
+        // place once done, so a crash never leaves `target` half-populated.
+        let staging = create_staging_dir(target_path)?;
+        let staging_target: String = normalize_path(&staging.path().to_string_lossy());
+        copy_all(&root, &staging_target, &config, &options)?;
+        commit_staging_dir(staging, target_path)?;
+    } else {
+        // Incremental run: copy straight into the existing target so docs
+        // directories placed there by a previous run accumulate instead of
+        // being discarded along with an empty staging directory.
+        fs::create_dir_all(target_path).map_err(|source| DocsHelperError::Io {
+            path: target_path.to_path_buf(),
+            source,
+        })?;
+        copy_all(&root, &target, &config, &options)?;
+    }
 
-    println!("\x1B[2K\rFound {} docs directories", total);
-    let mut done: u16 = 0;
-    let width = ((total as f64).log10().floor() as usize) + 1;
-    for entry_path in docs_dirs {
-        let relative = entry_path.strip_prefix(&root).unwrap();
+    println!("✅ All docs directories copied successfully.");
+    Ok(())
+}
 
-        std::io::Write::flush(&mut std::io::stdout()).unwrap();
-        let tartget_path_buf = PathBuf::from(&target);
-        // Construct the new path in the target directory, omitting the "docs" segment
-        let constructed_path: PathBuf = tartget_path_buf.join(relative.parent().unwrap());
-        let constructed_path_str = constructed_path.to_string_lossy();
-        let constructed_path_str = constructed_path_str.replace("/docs/", "/"); // Replace "/docs/" with "/"
-        let constructed_path = PathBuf::from(constructed_path_str);
+/// Performs the full `docs` directory scan and destination-path planning
+/// without touching the filesystem: no staging directory is created and
+/// neither `copy_docs_dir` nor `commit_staging_dir` run. Reuses
+/// `collect_docs_dirs` and `plan_destination` with the same `options` the
+/// real copy path uses, so the preview always matches what a real run would
+/// do, including `options.content_only`'s effect on the destination path.
+///
+/// The planned `source -> destination` mappings are emitted as
+/// newline-delimited JSON, either to `manifest_path` if given or to stdout
+/// otherwise.
+///
+/// # Errors
+///
+/// Returns a `DocsHelperError` if a destination cannot be planned for some
+/// docs directory, or if writing the manifest file fails.
+fn run_dry_run(
+    root: &str,
+    target: &str,
+    config: &DocsHelperConfig,
+    options: &CopyOptions,
+    manifest_path: Option<&Path>,
+) -> Result<(), DocsHelperError> {
+    let docs_dirs = collect_docs_dirs(root, config);
 
-        // Ensure parent directories exist before copying
-        if let Some(parent) = constructed_path.parent() {
-            if let Err(e) = fs::create_dir_all(parent) {
-                eprintln!("failed to create parent {}: {}", parent.display(), e);
-                continue;
-            }
-        }
-        // Perform the copy operation
-        if let Err(e) = copy_docs_dir(
-            entry_path.to_str().unwrap(),
-            constructed_path.to_str().unwrap(),
-        ) {
-            eprintln!(
-                "failed copying {} -> {}: {}",
-                entry_path.display(),
-                constructed_path.display(),
-                e
-            );
-        } else {
-            // Increment counter and print progress
-            done += 1;
+    let mut lines = Vec::with_capacity(docs_dirs.len());
+    for entry_path in &docs_dirs {
+        let destination = plan_destination(entry_path, root, target, config, options)?;
+        let entry = ManifestEntry {
+            source: entry_path.to_string_lossy().to_string(),
+            destination: destination.to_string_lossy().to_string(),
+        };
+        let line = serde_json::to_string(&entry).map_err(|e| DocsHelperError::InvalidPath {
+            path: entry_path.clone(),
+            message: e.to_string(),
+        })?;
+        lines.push(line);
+    }
 
+    let manifest = lines.join("\n");
+    match manifest_path {
+        Some(path) => {
+            fs::write(path, format!("{}\n", manifest)).map_err(|source| DocsHelperError::Io {
+                path: path.to_path_buf(),
+                source,
+            })?;
             println!(
-                "({:0width$}/{:0width$}) finished copying {} ",
-                done,
-                total,
-                relative.display()
+                "wrote manifest for {} docs directories to {}",
+                docs_dirs.len(),
+                path.display()
             );
         }
+        None => println!("{}", manifest),
     }
 
-    println!("✅ All docs directories copied successfully.");
     Ok(())
 }
@@ -1,63 +1,182 @@
-use std::fmt::format;
+use std::fs;
+use std::path::Path;
 
 use super::*;
 
 #[test]
-fn test_get_files_in_folder() {
-    // Create a temporary directory with some files and folders
+fn copy_docs_dir_copies_nested_contents() {
     let temp_dir = tempfile::tempdir().unwrap();
-    let temp_dir_path = temp_dir.path();
-    let file1_path = temp_dir_path.join("file1.txt");
-    fs::File::create(&file1_path).unwrap();
-    let dir1_path = temp_dir_path.join("dir1");
-    fs::create_dir(&dir1_path).unwrap();
-    let file2_path = dir1_path.join("file2.txt");
-    fs::File::create(&file2_path).unwrap();
+    let src_dir = temp_dir.path().join("docs");
+    fs::create_dir_all(src_dir.join("sub")).unwrap();
+    fs::write(src_dir.join("a.txt"), "a").unwrap();
+    fs::write(src_dir.join("sub/b.txt"), "b").unwrap();
 
-    // Call the function to get files in the temporary directory
-    let result = get_files_in_folder(temp_dir_path.to_str().unwrap()).unwrap();
+    let dest_dir = temp_dir.path().join("dest");
+    copy_docs_dir(
+        src_dir.to_str().unwrap(),
+        dest_dir.to_str().unwrap(),
+        &CopyOptions::default(),
+    )
+    .unwrap();
 
-    // Check if the result contains the expected paths
-    assert_eq!(result.len(), 2);
-    assert!(result.contains(&file1_path));
-    // assert!(result.contains(&file2_path));
+    assert!(dest_dir.join("a.txt").exists());
+    assert!(dest_dir.join("sub/b.txt").exists());
 }
 
+#[test]
+fn copy_docs_dir_errors_on_conflict_without_overwrite_or_skip_existing() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let src_dir = temp_dir.path().join("docs");
+    fs::create_dir_all(&src_dir).unwrap();
+    fs::write(src_dir.join("a.txt"), "new").unwrap();
+
+    let dest_dir = temp_dir.path().join("dest");
+    fs::create_dir_all(&dest_dir).unwrap();
+    fs::write(dest_dir.join("a.txt"), "old").unwrap();
+
+    let options = CopyOptions {
+        overwrite: false,
+        skip_existing: false,
+        content_only: true,
+        max_depth: None,
+    };
+    let result = copy_docs_dir(src_dir.to_str().unwrap(), dest_dir.to_str().unwrap(), &options);
+
+    assert!(result.is_err());
+    assert_eq!(fs::read_to_string(dest_dir.join("a.txt")).unwrap(), "old");
+}
+
+#[test]
+fn copy_docs_dir_skips_existing_when_skip_existing_is_set() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let src_dir = temp_dir.path().join("docs");
+    fs::create_dir_all(&src_dir).unwrap();
+    fs::write(src_dir.join("a.txt"), "new").unwrap();
+
+    let dest_dir = temp_dir.path().join("dest");
+    fs::create_dir_all(&dest_dir).unwrap();
+    fs::write(dest_dir.join("a.txt"), "old").unwrap();
 
+    let options = CopyOptions {
+        overwrite: false,
+        skip_existing: true,
+        content_only: true,
+        max_depth: None,
+    };
+    copy_docs_dir(src_dir.to_str().unwrap(), dest_dir.to_str().unwrap(), &options).unwrap();
+
+    assert_eq!(fs::read_to_string(dest_dir.join("a.txt")).unwrap(), "old");
+}
+
+/// Regression test for the incremental-run fix: copying into the same
+/// target twice with `overwrite: false` must accumulate both runs' docs
+/// directories rather than the second run wiping out the first's output.
 #[test]
-fn test_full_script() {
+fn copy_all_into_same_target_twice_accumulates() {
+    let temp_dir = tempfile::tempdir().unwrap();
+
+    let root1 = temp_dir.path().join("root1");
+    fs::create_dir_all(root1.join("docs")).unwrap();
+    fs::write(root1.join("docs/one.txt"), "one").unwrap();
+
+    let root2 = temp_dir.path().join("root2");
+    fs::create_dir_all(root2.join("docs")).unwrap();
+    fs::write(root2.join("docs/two.txt"), "two").unwrap();
+
+    let target = temp_dir.path().join("target");
+    fs::create_dir_all(&target).unwrap();
+
+    let config = DocsHelperConfig::default();
+    let options = CopyOptions {
+        overwrite: false,
+        skip_existing: true,
+        content_only: true,
+        max_depth: None,
+    };
+
+    copy_all(root1.to_str().unwrap(), target.to_str().unwrap(), &config, &options).unwrap();
+    copy_all(root2.to_str().unwrap(), target.to_str().unwrap(), &config, &options).unwrap();
+
+    assert!(target.join("one.txt").exists());
+    assert!(target.join("two.txt").exists());
+}
 
-    //Create source dir with docs fodler
+/// Regression test for the dry-run/real-run parity fix: `copy_all` must not
+/// let `copy_docs_dir` re-apply the `content_only` nesting decision that
+/// `plan_destination` already made, or a `--no-content-only` run ends up
+/// with the docs directory's name doubled in the destination path.
+#[test]
+fn copy_all_without_content_only_does_not_double_nest_docs_segment() {
     let temp_dir = tempfile::tempdir().unwrap();
-    let source_dir_path = temp_dir.path().join("source");
-    fs::create_dir(&source_dir_path).unwrap();
-    let docs_dir_path = source_dir_path.join("docs");
-    fs::create_dir(&docs_dir_path).unwrap();
-    let doc1_path = docs_dir_path.join("doc1.txt");
-    fs::File::create(&doc1_path).unwrap();
-
-    //Create subfolder in the source folder
-
-    let subfolder_dir_path = source_dir_path.join("subfolder");
-    fs::create_dir(&subfolder_dir_path).unwrap();
-    let subfolder_docs_dir_path = subfolder_dir_path.join("docs");
-    fs::create_dir(&subfolder_docs_dir_path).unwrap();
-    let doc2_path = subfolder_docs_dir_path.join("doc2.txt");
-    fs::File::create(&doc2_path).unwrap();
-
-    // Create a temporary destination directory 
-    let destination_dir_path = temp_dir.path().join("destination");
-
-
-    println!("SOURCE: {}\nDESTINATION: {}",source_dir_path.display(),destination_dir_path.display());
-    helper(source_dir_path.to_str().unwrap(),destination_dir_path.to_str().unwrap());
-    let dest_str = destination_dir_path.to_str().unwrap();
-    let src_str = source_dir_path.to_str().unwrap();
-    let temp_str = format!("{}/source",dest_str);
-    let dest_doc1_path = Path::new(&temp_str).join("doc1.txt");
-    let sub_temp_str = format!("{}/source/subfolder",dest_str);
-    let dest_doc2_path = Path::new(&sub_temp_str).join("doc2.txt");
-    assert!(dest_doc1_path.exists());
-    assert!(dest_doc2_path.exists());
+
+    let root = temp_dir.path().join("root");
+    fs::create_dir_all(root.join("src/docs")).unwrap();
+    fs::write(root.join("src/docs/a.txt"), "a").unwrap();
+
+    let target = temp_dir.path().join("target");
+    let config = DocsHelperConfig::default();
+    let options = CopyOptions {
+        content_only: false,
+        ..CopyOptions::default()
+    };
+
+    copy_all(root.to_str().unwrap(), target.to_str().unwrap(), &config, &options).unwrap();
+
+    assert!(target.join("src/docs/a.txt").exists());
+    assert!(!target.join("src/docs/docs").exists());
+}
+
+#[test]
+fn plan_destination_strips_docs_segment() {
+    let entry = Path::new("/project/src/docs");
+    let config = DocsHelperConfig::default();
+    let dest = plan_destination(entry, "/project/", "/out/", &config, &CopyOptions::default()).unwrap();
+    assert_eq!(dest, Path::new("/out/src"));
+}
+
+/// Regression test for the hardcoded "/docs/" flattening bug: with a custom
+/// `find_dir`, every path component matching it must be collapsed out, not
+/// just a literal "docs" segment, including a nested match where a docs
+/// directory sits inside another docs directory.
+#[test]
+fn plan_destination_collapses_custom_find_dir_on_nested_match() {
+    let entry = Path::new("/project/a/documentation/b/documentation");
+    let config = DocsHelperConfig {
+        find_dir: "documentation".to_string(),
+        ..DocsHelperConfig::default()
+    };
+    let dest = plan_destination(entry, "/project/", "/out/", &config, &CopyOptions::default()).unwrap();
+    assert_eq!(dest, Path::new("/out/a/b"));
+}
+
+/// Regression test for the dry-run/real-run parity fix: with
+/// `content_only: false`, the planned destination must keep the docs
+/// directory's own name nested under it, matching `copy_docs_dir`'s
+/// equivalent branch.
+#[test]
+fn plan_destination_without_content_only_keeps_docs_segment() {
+    let entry = Path::new("/project/src/docs");
+    let options = CopyOptions {
+        content_only: false,
+        ..CopyOptions::default()
+    };
+    let config = DocsHelperConfig::default();
+    let dest = plan_destination(entry, "/project/", "/out/", &config, &options).unwrap();
+    assert_eq!(dest, Path::new("/out/src/docs"));
 }
 
+#[test]
+fn config_merges_additional_ignores_over_defaults() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    fs::write(
+        temp_dir.path().join("docs-helper.toml"),
+        "find_dir = \"documentation\"\nadditional_ignores = [\"vendor\"]\n",
+    )
+    .unwrap();
+
+    let config = DocsHelperConfig::load(temp_dir.path(), None).unwrap();
+
+    assert_eq!(config.find_dir, "documentation");
+    assert!(config.ignore_patterns.iter().any(|p| p == "vendor"));
+    assert!(config.ignore_patterns.iter().any(|p| p == "node_modules"));
+}